@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use crate::{error::ParseError, matches::Matches, opt::Opts};
+
+pub struct Command {
+    opts: Opts,
+    subcommands: HashMap<String, Opts>,
+}
+
+impl Command {
+    pub fn new(opts: Opts) -> Self {
+        Self {
+            opts,
+            subcommands: HashMap::new(),
+        }
+    }
+
+    pub fn subcommand(mut self, name: &str, opts: Opts) -> Self {
+        self.subcommands.insert(name.to_string(), opts);
+        self
+    }
+
+    pub fn parse(&self, args: Vec<String>) -> Result<Matches, ParseError> {
+        match self.find_split(&args) {
+            Some(idx) => {
+                let top_args = args[..idx].to_vec();
+                let name = args[idx].clone();
+                let mut child_args = vec![name.clone()];
+                child_args.extend(args[idx + 1..].iter().cloned());
+
+                let mut matches = self.opts.parse(top_args)?;
+                let child_opts = self
+                    .subcommands
+                    .get(&name)
+                    .expect("find_split only returns indices of registered subcommands");
+                let child_matches = child_opts.parse(child_args)?;
+                matches.set_subcommand(name, child_matches);
+                Ok(matches)
+            }
+            None => self.opts.parse(args),
+        }
+    }
+
+    fn find_split(&self, args: &[String]) -> Option<usize> {
+        let mut i = 1;
+        while i < args.len() {
+            let token = &args[i];
+            if token == "--" {
+                return None;
+            }
+            if let Some(long) = token.strip_prefix("--") {
+                let has_attached_value = long.contains('=');
+                if !has_attached_value {
+                    if let Some(opt) = self.opts.find_by_token(token) {
+                        if opt.takes_value() {
+                            i += 2;
+                            continue;
+                        }
+                    }
+                }
+                i += 1;
+                continue;
+            }
+            if let Some(rest) = token.strip_prefix('-') {
+                if !rest.is_empty() {
+                    if self.short_cluster_takes_external_value(rest) {
+                        i += 2;
+                    } else {
+                        i += 1;
+                    }
+                    continue;
+                }
+            }
+            return self.subcommands.contains_key(token).then_some(i);
+        }
+        None
+    }
+
+    fn short_cluster_takes_external_value(&self, rest: &str) -> bool {
+        for (idx, c) in rest.char_indices() {
+            let Some(opt) = self.opts.find_short_opt(c) else {
+                return false;
+            };
+            if opt.takes_value() {
+                let remainder = &rest[idx + c.len_utf8()..];
+                return remainder.is_empty();
+            }
+        }
+        false
+    }
+
+    pub fn usage(&self, brief: &str) -> String {
+        let mut out = self.opts.usage(brief);
+        if !self.subcommands.is_empty() {
+            let mut names: Vec<&String> = self.subcommands.keys().collect();
+            names.sort();
+            out.push_str("\nSubcommands:\n");
+            for name in names {
+                out.push_str(&format!("    {}\n", name));
+            }
+        }
+        out
+    }
+
+    pub fn subcommand_usage(&self, name: &str, brief: &str) -> Option<String> {
+        self.subcommands.get(name).map(|opts| opts.usage(brief))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opt::{Action, Opt};
+
+    fn strs(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn dispatches_to_matching_subcommand() {
+        let top = Opts::new(vec![Opt::name("verbose").long("verbose").action(Action::SetTrue)]).unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m')]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit);
+
+        let matches = cmd
+            .parse(strs(&["git", "--verbose", "commit", "-m", "hello"]))
+            .unwrap();
+
+        assert_eq!(matches.flag("verbose").unwrap(), Some(true));
+        let (name, sub) = matches.subcommand().expect("subcommand should have fired");
+        assert_eq!(name, "commit");
+        assert_eq!(sub.one("message").unwrap(), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn no_subcommand_token_parses_as_top_level() {
+        let top = Opts::new(vec![Opt::name("verbose").long("verbose").action(Action::SetTrue)]).unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m')]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit);
+
+        let matches = cmd.parse(strs(&["git", "--verbose", "status"])).unwrap();
+
+        assert!(matches.subcommand().is_none());
+        assert_eq!(matches.positional(), vec!["status".to_string()]);
+    }
+
+    #[test]
+    fn dispatches_past_clustered_short_flags_with_external_value() {
+        let top = Opts::new(vec![
+            Opt::name("extract").short('x').action(Action::SetTrue),
+            Opt::name("verbose").short('v').action(Action::SetTrue),
+            Opt::name("file").short('f'),
+        ])
+        .unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m')]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit);
+
+        let matches = cmd
+            .parse(strs(&[
+                "prog",
+                "-xvf",
+                "archive.tar",
+                "commit",
+                "-m",
+                "hello",
+            ]))
+            .unwrap();
+
+        assert_eq!(matches.flag("extract").unwrap(), Some(true));
+        assert_eq!(matches.flag("verbose").unwrap(), Some(true));
+        assert_eq!(matches.one("file").unwrap(), Some("archive.tar".to_string()));
+        let (name, sub) = matches.subcommand().expect("subcommand should have fired");
+        assert_eq!(name, "commit");
+        assert_eq!(sub.one("message").unwrap(), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn required_option_enforced_within_subcommand() {
+        let top = Opts::new(vec![]).unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m').required(true)]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit);
+
+        let err = cmd.parse(strs(&["git", "commit"])).unwrap_err();
+        assert!(matches!(err, ParseError::MissingRequired(name) if name == "message"));
+    }
+
+    #[test]
+    fn usage_lists_subcommand_names() {
+        let top = Opts::new(vec![Opt::name("verbose").long("verbose").action(Action::SetTrue)]).unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m')]).unwrap();
+        let push = Opts::new(vec![]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit).subcommand("push", push);
+
+        let usage = cmd.usage("git [OPTIONS] <COMMAND>");
+
+        assert!(usage.contains("git [OPTIONS] <COMMAND>"));
+        assert!(usage.contains("--verbose"));
+        assert!(usage.contains("Subcommands:"));
+        assert!(usage.contains("commit"));
+        assert!(usage.contains("push"));
+    }
+
+    #[test]
+    fn subcommand_usage_delegates_to_child_opts() {
+        let top = Opts::new(vec![]).unwrap();
+        let commit = Opts::new(vec![Opt::name("message").short('m').help("commit message")]).unwrap();
+        let cmd = Command::new(top).subcommand("commit", commit);
+
+        let usage = cmd.subcommand_usage("commit", "git commit [OPTIONS]").unwrap();
+
+        assert!(usage.contains("git commit [OPTIONS]"));
+        assert!(usage.contains("commit message"));
+        assert!(cmd.subcommand_usage("bogus", "x").is_none());
+    }
+}
@@ -3,9 +3,11 @@ use std::{error::Error, fmt::Display};
 #[derive(Debug)]
 pub enum ParseError {
     MalformedOption(String),
-    UnexpectedOption(String),
+    UnexpectedOption(String, Option<String>),
     MissingProgramName,
     MissingValue(String),
+    MissingRequired(String),
+    RequiresOption(String, String),
     BadInternalState,
 }
 
@@ -19,8 +21,23 @@ impl Display for ParseError {
                 write!(f, "bad internal state, possibly bug in opts lib")
             }
             ParseError::MalformedOption(arg) => write!(f, "malformed option; got '{}'", arg),
-            ParseError::UnexpectedOption(arg) => write!(f, "unexpected option; got '{}'", arg),
+            ParseError::UnexpectedOption(arg, None) => {
+                write!(f, "unexpected option; got '{}'", arg)
+            }
+            ParseError::UnexpectedOption(arg, Some(suggestion)) => write!(
+                f,
+                "unexpected option '{}'; did you mean '{}'?",
+                arg, suggestion
+            ),
             ParseError::MissingValue(arg) => write!(f, "missinfg value for {}", arg),
+            ParseError::MissingRequired(name) => {
+                write!(f, "missing required option '{}'", name)
+            }
+            ParseError::RequiresOption(name, requires) => write!(
+                f,
+                "option '{}' requires '{}', which was not provided",
+                name, requires
+            ),
         }
     }
 }
@@ -4,16 +4,27 @@ use crate::{error::ValueError, opt::Value};
 
 #[derive(Debug, PartialEq)]
 pub struct Matches {
-    exec_name: String, 
-    positional: Vec<String>, 
+    exec_name: String,
+    positional: Vec<String>,
     named: HashMap<String, Value>,
+    subcommand: Option<(String, Box<Matches>)>,
 }
 
 impl Matches {
     pub fn new(exec_name: String, positional: Vec<String>, named: HashMap<String, Value>) -> Self {
-        Self {exec_name, positional, named}
+        Self {exec_name, positional, named, subcommand: None}
     }
-    
+
+    pub(crate) fn set_subcommand(&mut self, name: String, matches: Matches) {
+        self.subcommand = Some((name, Box::new(matches)));
+    }
+
+    pub fn subcommand(&self) -> Option<(&str, &Matches)> {
+        self.subcommand
+            .as_ref()
+            .map(|(name, matches)| (name.as_str(), matches.as_ref()))
+    }
+
     pub fn flag(&self, name: &str) -> Result<Option<bool>, ValueError> {
         match self.named.get(name) {
             Some(Value::Flag(b)) => Ok(Some(*b)),
@@ -45,6 +56,14 @@ impl Matches {
         }
     }
 
+    pub fn count(&self, name: &str) -> Result<u32, ValueError> {
+        match self.named.get(name) {
+            Some(Value::Count(n)) => Ok(*n),
+            Some(_) => Err(ValueError::WrongOptionType),
+            None => Ok(0),
+        }
+    }
+
     pub fn positional(&self) -> &[String] {
         &self.positional
     }
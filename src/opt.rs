@@ -4,26 +4,28 @@ use crate::{error::ParseError, matches::Matches};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Opt {
-    pub name: String, 
-    pub short: Option<char>, 
-    pub long: Option<String>, 
+    pub name: String,
+    pub short: Option<char>,
+    pub long: Option<String>,
     pub help: Option<String>,
-    pub default: Option<String>, 
-    pub action: Action, 
+    pub default: Option<String>,
+    pub action: Action,
     pub required: bool,
+    pub requires: Vec<String>,
 }
 
 impl Opt {
 
     pub fn name(name: &str) -> Self {
         Self {
-            name: name.into(), 
-            short:None, 
-            long: None, 
-            help: None, 
-            default: None, 
+            name: name.into(),
+            short:None,
+            long: None,
+            help: None,
+            default: None,
                      action: Action::Set,
-                     required:false
+                     required:false,
+                     requires: vec![],
         }
     }
 
@@ -57,21 +59,32 @@ impl Opt {
         self
     }
 
+    pub fn requires(mut self, name: &str) -> Self {
+        self.requires.push(name.into());
+        self
+    }
+
+    pub(crate) fn takes_value(&self) -> bool {
+        matches!(self.action, Action::Set | Action::Append)
+    }
+
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
-    Set, 
-    Append, 
+    Set,
+    Append,
     SetTrue,
     SetFalse,
+    Count,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
-    Single(String), 
+    Single(String),
     Multi(Vec<String>),
     Flag(bool),
+    Count(u32),
 }
 
 #[derive(Debug, PartialEq)]
@@ -95,54 +108,189 @@ impl Opts {
     pub fn parse(&self, args: Vec<String>) -> Result<Matches, ParseError> {
         let mut args = args.into_iter();
         let exec_name = match args.next() {
-            Some(s) => s, 
+            Some(s) => s,
             None => return Err(ParseError::MissingProgramName),
         };
         let mut positional = vec![];
         let mut named = HashMap::new();
+        let mut seen = HashSet::new();
         self.populate_defaults(&mut named);
         while let Some(arg) = args.next() {
-            if arg.starts_with("-") {
-                let opt = self.find_opt(&arg)?;
-                match opt.action {
-                    Action::Set      => {
-                        if let Some(value) = args.next() {
-                            named.insert(opt.name.clone(), Value::Single(value));
-                        } else {
-                            return Err(ParseError::MissingValue(opt.name.to_owned()));
-                        }
-                    },
-                    Action::Append   => {
-                        match (args.next(), named.get_mut(&opt.name)) {
-                            (None, _) => {
-                                return Err(ParseError::MissingValue(opt.name.clone()));
-                            }, 
-                            (Some(val), Some(Value::Multi(vals))) => {
-                                vals.push(val);
-                            },
-                            (Some(val), None) => {
-                                named.insert(opt.name.clone(), Value::Multi(vec![val]));
-                            },
-                            _ => return Err(ParseError::BadInternalState),
-                        }
-                    },
-                    Action::SetTrue  => {
-                        named.insert(opt.name.clone(), Value::Flag(true));
-                    },
-                    Action::SetFalse => {
-                        named.insert(opt.name.clone(), Value::Flag(false));
-                    },
+            if arg == "--" {
+                positional.extend(args.by_ref());
+                continue;
+            }
+            if let Some(long) = arg.strip_prefix("--") {
+                let (name, attached) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let opt = self.find_long(name)?;
+                self.set_named(opt, attached, &mut args, &mut named, &mut seen)?;
+            } else if let Some(rest) = arg.strip_prefix('-') {
+                if rest.is_empty() {
+                    return Err(ParseError::MalformedOption(arg));
                 }
+                self.parse_short_cluster(rest, &mut args, &mut named, &mut seen)?;
             } else {
                 positional.push(arg);
             }
         }
+        self.check_required(&seen)?;
+        self.check_requires(&seen)?;
         Ok(Matches::new(exec_name, positional, named))
     }
+
+    fn is_present(&self, name: &str, seen: &HashSet<String>) -> bool {
+        if seen.contains(name) {
+            return true;
+        }
+        self.opts
+            .iter()
+            .find(|o| o.name == name)
+            .is_some_and(|o| o.default.is_some())
+    }
+
+    fn check_required(&self, seen: &HashSet<String>) -> Result<(), ParseError> {
+        for opt in &self.opts {
+            if opt.required && !self.is_present(&opt.name, seen) {
+                return Err(ParseError::MissingRequired(opt.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_requires(&self, seen: &HashSet<String>) -> Result<(), ParseError> {
+        for opt in &self.opts {
+            if !self.is_present(&opt.name, seen) {
+                continue;
+            }
+            for dependency in &opt.requires {
+                if !self.is_present(dependency, seen) {
+                    return Err(ParseError::RequiresOption(
+                        opt.name.clone(),
+                        dependency.clone(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_named(
+        &self,
+        opt: &Opt,
+        attached: Option<String>,
+        args: &mut std::vec::IntoIter<String>,
+        named: &mut HashMap<String, Value>,
+        seen: &mut HashSet<String>,
+    ) -> Result<(), ParseError> {
+        seen.insert(opt.name.clone());
+        match opt.action {
+            Action::Set => {
+                let value = match attached {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| ParseError::MissingValue(opt.name.clone()))?,
+                };
+                named.insert(opt.name.clone(), Value::Single(value));
+            }
+            Action::Append => {
+                let value = match attached {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| ParseError::MissingValue(opt.name.clone()))?,
+                };
+                match named.get_mut(&opt.name) {
+                    Some(Value::Multi(vals)) => vals.push(value),
+                    None => {
+                        named.insert(opt.name.clone(), Value::Multi(vec![value]));
+                    }
+                    _ => return Err(ParseError::BadInternalState),
+                }
+            }
+            Action::SetTrue => {
+                if let Some(value) = attached {
+                    return Err(ParseError::MalformedOption(format!(
+                        "--{}={}",
+                        opt.long.as_deref().unwrap_or(&opt.name),
+                        value
+                    )));
+                }
+                named.insert(opt.name.clone(), Value::Flag(true));
+            }
+            Action::SetFalse => {
+                if let Some(value) = attached {
+                    return Err(ParseError::MalformedOption(format!(
+                        "--{}={}",
+                        opt.long.as_deref().unwrap_or(&opt.name),
+                        value
+                    )));
+                }
+                named.insert(opt.name.clone(), Value::Flag(false));
+            }
+            Action::Count => {
+                if let Some(value) = attached {
+                    return Err(ParseError::MalformedOption(format!(
+                        "--{}={}",
+                        opt.long.as_deref().unwrap_or(&opt.name),
+                        value
+                    )));
+                }
+                match named.get_mut(&opt.name) {
+                    Some(Value::Count(n)) => *n += 1,
+                    _ => {
+                        named.insert(opt.name.clone(), Value::Count(1));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_short_cluster(
+        &self,
+        rest: &str,
+        args: &mut std::vec::IntoIter<String>,
+        named: &mut HashMap<String, Value>,
+        seen: &mut HashSet<String>,
+    ) -> Result<(), ParseError> {
+        for (idx, c) in rest.char_indices() {
+            let opt = self.find_short(c)?;
+            match opt.action {
+                Action::SetTrue | Action::SetFalse | Action::Count => {
+                    self.set_named(opt, None, args, named, seen)?;
+                }
+                Action::Set | Action::Append => {
+                    let remainder = &rest[idx + c.len_utf8()..];
+                    let attached = if remainder.is_empty() {
+                        None
+                    } else {
+                        Some(remainder.to_string())
+                    };
+                    self.set_named(opt, attached, args, named, seen)?;
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
     pub fn populate_defaults(&self, named: &mut HashMap<String, Value>) {
         for opt in &self.opts {
             if let Some(default) = &opt.default {
-                named.insert(opt.name.clone(), Value::Single(default.to_owned()));
+                let value = match opt.action {
+                    Action::Append => Value::Multi(vec![default.to_owned()]),
+                    Action::Count => {
+                        Value::Count(default.parse().expect("validated by Opts::validate"))
+                    }
+                    Action::Set => Value::Single(default.to_owned()),
+                    Action::SetTrue | Action::SetFalse => Value::Flag(
+                        default.parse().expect("validated by Opts::validate"),
+                    ),
+                };
+                named.insert(opt.name.clone(), value);
             } else {
                 match opt.action {
                     Action::Set => {}, 
@@ -155,29 +303,66 @@ impl Opts {
                     Action::SetFalse => {
                         named.insert(opt.name.clone(), Value::Flag(false));
                     },
+                    Action::Count => {
+                        named.insert(opt.name.clone(), Value::Count(0));
+                    },
                 }
             }
         }
     }
-    fn find_opt(&self, arg: &str) -> Result<&Opt, ParseError> {
-        let opt = if arg.starts_with("--") {
-            let long = arg.strip_prefix("--").unwrap();
-            self.opts.iter().find(|o| o.long.as_deref() == Some(long))
-        } else if arg.starts_with("-") {
-            if arg.chars().count() != 2 {
-                return Err(ParseError::MalformedOption(arg.to_string()));
-            }
-            let short = arg.chars().nth(1);
-            self.opts.iter().find(|o| o.short == short)
-        } else {
-            return Err(ParseError::UnexpectedOption(arg.to_string()));
-        };
-        if let Some(opt) = opt {
-            Ok(opt)
+    pub(crate) fn find_by_token(&self, token: &str) -> Option<&Opt> {
+        if let Some(long) = token.strip_prefix("--") {
+            let name = long.split_once('=').map_or(long, |(name, _)| name);
+            self.opts.iter().find(|o| o.long.as_deref() == Some(name))
+        } else if let Some(rest) = token.strip_prefix('-') {
+            let short = rest.chars().next()?;
+            self.find_short_opt(short)
         } else {
-            Err(ParseError::UnexpectedOption(arg.to_string()))
+            None
         }
     }
+
+    pub(crate) fn find_short_opt(&self, c: char) -> Option<&Opt> {
+        self.opts.iter().find(|o| o.short == Some(c))
+    }
+
+    fn find_long(&self, long: &str) -> Result<&Opt, ParseError> {
+        self.opts
+            .iter()
+            .find(|o| o.long.as_deref() == Some(long))
+            .ok_or_else(|| {
+                let suggestion = self.suggest_long(long).map(|s| format!("--{}", s));
+                ParseError::UnexpectedOption(format!("--{}", long), suggestion)
+            })
+    }
+
+    fn find_short(&self, short: char) -> Result<&Opt, ParseError> {
+        self.opts
+            .iter()
+            .find(|o| o.short == Some(short))
+            .ok_or_else(|| {
+                let suggestion = self.suggest_short(short).map(|c| format!("-{}", c));
+                ParseError::UnexpectedOption(format!("-{}", short), suggestion)
+            })
+    }
+
+    fn suggest_long(&self, name: &str) -> Option<String> {
+        self.opts
+            .iter()
+            .filter_map(|o| o.long.as_deref())
+            .map(|candidate| (candidate, jaro_winkler(name, candidate)))
+            .filter(|(_, score)| *score > SUGGESTION_THRESHOLD)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(candidate, _)| candidate.to_string())
+    }
+
+    // Jaro-Winkler on single chars only ever scores 0 or 1, so it can't rank candidates; keyboard adjacency (the keys either side in the same row, plus the nearest key in the row above and below; other diagonal neighbors are not counted) gives a usable "did you mean" instead.
+    fn suggest_short(&self, c: char) -> Option<char> {
+        self.opts
+            .iter()
+            .filter_map(|o| o.short)
+            .find(|&candidate| keyboard_adjacent(c, candidate))
+    }
     fn validate(&self) -> Result<(), String> {
         let mut names: HashSet<String> = HashSet::new();
         let mut short: HashSet<char>   = HashSet::new();
@@ -208,10 +393,222 @@ impl Opts {
             if let Some(s) = &arg.long {
                 long.insert(s.to_string());
             }
+
+            if let Some(default) = &arg.default {
+                match arg.action {
+                    Action::Count => {
+                        if default.parse::<u32>().is_err() {
+                            return Err(format!(
+                                "Default for {} must be a valid count; found {:?}",
+                                arg.name, default
+                            ));
+                        }
+                    }
+                    Action::SetTrue | Action::SetFalse => {
+                        if default.parse::<bool>().is_err() {
+                            return Err(format!(
+                                "Default for {} must be \"true\" or \"false\"; found {:?}",
+                                arg.name, default
+                            ));
+                        }
+                    }
+                    Action::Set | Action::Append => {}
+                }
+            }
         }
 
         Ok(())
     }
+
+    pub fn usage(&self, brief: &str) -> String {
+        let rows: Vec<(String, String)> = self
+            .opts
+            .iter()
+            .map(|opt| (Self::usage_left(opt), Self::usage_right(opt)))
+            .collect();
+        let col_width = rows.iter().map(|(left, _)| left.chars().count()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        out.push_str(brief);
+        out.push('\n');
+        if !rows.is_empty() {
+            out.push_str("\nOptions:\n");
+        }
+
+        const INDENT: usize = 4;
+        const GAP: usize = 4;
+        let wrap_width = DEFAULT_WIDTH.saturating_sub(INDENT + col_width + GAP).max(20);
+        for (left, right) in rows {
+            if right.is_empty() {
+                out.push_str(&format!("{:indent$}{}\n", "", left, indent = INDENT));
+                continue;
+            }
+            for (i, line) in wrap_text(&right, wrap_width).into_iter().enumerate() {
+                if i == 0 {
+                    out.push_str(&format!(
+                        "{:indent$}{:col_width$}{:gap$}{}\n",
+                        "",
+                        left,
+                        "",
+                        line,
+                        indent = INDENT,
+                        col_width = col_width,
+                        gap = GAP
+                    ));
+                } else {
+                    out.push_str(&format!("{:width$}{}\n", "", line, width = INDENT + col_width + GAP));
+                }
+            }
+        }
+        out
+    }
+
+    fn usage_left(opt: &Opt) -> String {
+        let mut names = vec![];
+        if let Some(c) = opt.short {
+            names.push(format!("-{}", c));
+        }
+        if let Some(l) = &opt.long {
+            names.push(format!("--{}", l));
+        }
+        let mut left = names.join(", ");
+        match opt.action {
+            Action::Set => left.push_str(&format!(" <{}>", opt.name.to_uppercase())),
+            Action::Append => left.push_str(&format!(" <{}>...", opt.name.to_uppercase())),
+            Action::Count => left.push_str(" (repeatable)"),
+            Action::SetTrue | Action::SetFalse => {}
+        }
+        left
+    }
+
+    fn usage_right(opt: &Opt) -> String {
+        let mut text = opt.help.clone().unwrap_or_default();
+        if let Some(default) = &opt.default {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&format!("(default: {})", default));
+        }
+        text
+    }
+}
+
+const DEFAULT_WIDTH: usize = 78;
+
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current.push_str(word);
+        } else if current.chars().count() + 1 + word.chars().count() <= width {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+const SUGGESTION_THRESHOLD: f64 = 0.7;
+
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// Row stagger on a physical QWERTY keyboard: each row shifts right by about
+// a quarter key relative to the one above it.
+const ROW_STAGGER: f64 = 0.25;
+const ADJACENCY_DISTANCE: f64 = 1.2;
+
+fn key_position(c: char) -> Option<(f64, f64)> {
+    KEYBOARD_ROWS.iter().enumerate().find_map(|(row, keys)| {
+        keys.chars()
+            .position(|k| k == c)
+            .map(|col| (col as f64 + row as f64 * ROW_STAGGER, row as f64))
+    })
+}
+
+fn keyboard_adjacent(a: char, b: char) -> bool {
+    if a == b {
+        return false;
+    }
+    match (key_position(a), key_position(b)) {
+        (Some((x1, y1)), Some((x2, y2))) => {
+            let dx = x1 - x2;
+            let dy = y1 - y2;
+            (dx * dx + dy * dy).sqrt() < ADJACENCY_DISTANCE
+        }
+        _ => false,
+    }
+}
+
+fn jaro(s1: &str, s2: &str) -> f64 {
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    if s1.is_empty() && s2.is_empty() {
+        return 1.0;
+    }
+    if s1.is_empty() || s2.is_empty() {
+        return 0.0;
+    }
+
+    let match_distance = s1.len().max(s2.len()) / 2;
+    let match_distance = match_distance.saturating_sub(1);
+
+    let mut s1_matches = vec![false; s1.len()];
+    let mut s2_matches = vec![false; s2.len()];
+    let mut matches = 0;
+    for (i, c1) in s1.iter().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(s2.len());
+        for (j, matched) in s2_matches.iter_mut().enumerate().take(end).skip(start) {
+            if *matched || s2[j] != *c1 {
+                continue;
+            }
+            *matched = true;
+            s1_matches[i] = true;
+            matches += 1;
+            break;
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0;
+    let mut k = 0;
+    for (i, matched) in s1_matches.iter().enumerate() {
+        if !matched {
+            continue;
+        }
+        while !s2_matches[k] {
+            k += 1;
+        }
+        if s1[i] != s2[k] {
+            transpositions += 1;
+        }
+        k += 1;
+    }
+    let transpositions = transpositions as f64 / 2.0;
+    let matches = matches as f64;
+
+    (matches / s1.len() as f64 + matches / s2.len() as f64 + (matches - transpositions) / matches)
+        / 3.0
+}
+
+fn jaro_winkler(s1: &str, s2: &str) -> f64 {
+    let jaro = jaro(s1, s2);
+    let prefix_len = s1
+        .chars()
+        .zip(s2.chars())
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+    jaro + (prefix_len as f64 * 0.1 * (1.0 - jaro))
 }
 
 #[cfg(test)]
@@ -268,6 +665,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detects_malformed_count_default() {
+        let opts = Opts::new(vec![Opt::name("verbosity")
+            .short('v')
+            .action(Action::Count)
+            .default("two")]);
+        assert_eq!(
+            opts,
+            Err(format!(
+                "Default for verbosity must be a valid count; found \"two\""
+            ))
+        );
+    }
+
+    #[test]
+    fn detects_malformed_flag_default() {
+        let opts = Opts::new(vec![Opt::name("verbose")
+            .long("verbose")
+            .action(Action::SetTrue)
+            .default("yes")]);
+        assert_eq!(
+            opts,
+            Err(format!(
+                "Default for verbose must be \"true\" or \"false\"; found \"yes\""
+            ))
+        );
+    }
+
     #[test]
     fn parses_positional_args() {
         let opts = Opts::new(vec![Opt::name("host").long("host")]).unwrap();
@@ -297,7 +722,7 @@ mod tests {
             Opt::name("missing").default("something"),
         ])
         .unwrap();
-        let args: Vec<String> = vec![
+        let args: Vec<String> = [
             "myprogram",
             "1",
             "2",
@@ -336,4 +761,328 @@ mod tests {
             Some("something".to_string())
         );
     }
+
+    #[test]
+    fn parses_long_value_with_equals() {
+        let opts = Opts::new(vec![Opt::name("host").long("host")]).unwrap();
+        let args: Vec<String> = ["myprogram", "--host=localhost"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.one("host").unwrap(), Some("localhost".to_string()));
+    }
+
+    #[test]
+    fn parses_short_value_attached() {
+        let opts = Opts::new(vec![Opt::name("queue").short('q').action(Action::Append)]).unwrap();
+        let args: Vec<String> = ["myprogram", "-qitems"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        let queues: Vec<String> = matches.all("queue").unwrap();
+        assert_eq!(queues, vec!["items".to_string()]);
+    }
+
+    #[test]
+    fn parses_clustered_short_flags() {
+        let opts = Opts::new(vec![
+            Opt::name("extract").short('x').action(Action::SetTrue),
+            Opt::name("verbose").short('v').action(Action::SetTrue),
+            Opt::name("file").short('f'),
+        ])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "-xvf", "archive.tar"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.flag("extract").unwrap(), Some(true));
+        assert_eq!(matches.flag("verbose").unwrap(), Some(true));
+        assert_eq!(matches.one("file").unwrap(), Some("archive.tar".to_string()));
+    }
+
+    #[test]
+    fn rejects_value_attached_to_flag_action() {
+        let opts = Opts::new(vec![Opt::name("verbose")
+            .long("verbose")
+            .action(Action::SetTrue)])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "--verbose=true"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let err = opts.parse(args).unwrap_err();
+        assert!(matches!(err, ParseError::MalformedOption(_)));
+    }
+
+    #[test]
+    fn renders_usage() {
+        let opts = Opts::new(vec![
+            Opt::name("host")
+                .short('h')
+                .long("host")
+                .help("the host to connect to")
+                .default("localhost"),
+            Opt::name("verbose")
+                .long("verbose")
+                .action(Action::SetTrue)
+                .help("enable verbose output"),
+            Opt::name("queue")
+                .short('q')
+                .action(Action::Append)
+                .help("a queue to drain"),
+        ])
+        .unwrap();
+
+        let usage = opts.usage("myprogram [OPTIONS]");
+        assert!(usage.starts_with("myprogram [OPTIONS]\n"));
+        assert!(usage.contains("-h, --host <HOST>"));
+        assert!(usage.contains("the host to connect to (default: localhost)"));
+        assert!(usage.contains("--verbose"));
+        assert!(usage.contains("enable verbose output"));
+        assert!(usage.contains("-q <QUEUE>..."));
+    }
+
+    #[test]
+    fn rejects_missing_required_option() {
+        let opts = Opts::new(vec![Opt::name("host").long("host").required(true)]).unwrap();
+        let args: Vec<String> = ["myprogram"].iter().map(|s| s.to_string()).collect();
+
+        let err = opts.parse(args).unwrap_err();
+        assert!(matches!(err, ParseError::MissingRequired(name) if name == "host"));
+    }
+
+    #[test]
+    fn required_option_with_default_is_satisfied() {
+        let opts = Opts::new(vec![Opt::name("host")
+            .long("host")
+            .required(true)
+            .default("localhost")])
+        .unwrap();
+        let args: Vec<String> = ["myprogram"].iter().map(|s| s.to_string()).collect();
+
+        assert!(opts.parse(args).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_required_append_option() {
+        let opts = Opts::new(vec![Opt::name("include")
+            .short('i')
+            .action(Action::Append)
+            .required(true)])
+        .unwrap();
+        let args: Vec<String> = ["myprogram"].iter().map(|s| s.to_string()).collect();
+
+        let err = opts.parse(args).unwrap_err();
+        assert!(matches!(err, ParseError::MissingRequired(name) if name == "include"));
+    }
+
+    #[test]
+    fn satisfies_required_append_option_when_provided() {
+        let opts = Opts::new(vec![Opt::name("include")
+            .short('i')
+            .action(Action::Append)
+            .required(true)])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "-i", "src"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.all::<String>("include").unwrap(), vec!["src".to_string()]);
+    }
+
+    #[test]
+    fn append_option_with_default_is_extended_when_provided() {
+        let opts = Opts::new(vec![Opt::name("include")
+            .short('i')
+            .action(Action::Append)
+            .default("x")])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "-i", "src"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(
+            matches.all::<String>("include").unwrap(),
+            vec!["x".to_string(), "src".to_string()]
+        );
+    }
+
+    #[test]
+    fn count_option_with_default_is_incremented_when_provided() {
+        let opts = Opts::new(vec![Opt::name("verbosity")
+            .short('v')
+            .action(Action::Count)
+            .default("2")])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "-v"].iter().map(|s| s.to_string()).collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.count("verbosity").unwrap(), 3);
+    }
+
+    #[test]
+    fn flag_option_with_default_is_readable_as_flag_when_absent() {
+        let opts = Opts::new(vec![Opt::name("verbose")
+            .long("verbose")
+            .action(Action::SetTrue)
+            .default("true")])
+        .unwrap();
+        let args: Vec<String> = ["myprogram"].iter().map(|s| s.to_string()).collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.flag("verbose").unwrap(), Some(true));
+    }
+
+    #[test]
+    fn rejects_missing_requires_dependency() {
+        let opts = Opts::new(vec![
+            Opt::name("tls").long("tls").action(Action::SetTrue),
+            Opt::name("cert").long("cert").requires("tls_key"),
+            Opt::name("tls_key").long("tls-key"),
+        ])
+        .unwrap();
+        let args: Vec<String> = ["myprogram", "--cert", "a.pem"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let err = opts.parse(args).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::RequiresOption(name, dep) if name == "cert" && dep == "tls_key"
+        ));
+    }
+
+    #[test]
+    fn suggests_close_long_option() {
+        let opts = Opts::new(vec![Opt::name("host").long("host")]).unwrap();
+        let args: Vec<String> = ["myprogram", "--hsot", "x"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let err = opts.parse(args).unwrap_err();
+        match err {
+            ParseError::UnexpectedOption(arg, suggestion) => {
+                assert_eq!(arg, "--hsot");
+                assert_eq!(suggestion, Some("--host".to_string()));
+            }
+            other => panic!("expected UnexpectedOption, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn does_not_suggest_unrelated_option() {
+        let opts = Opts::new(vec![Opt::name("host").long("host")]).unwrap();
+        let args: Vec<String> = ["myprogram", "--zzzzzzz"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let err = opts.parse(args).unwrap_err();
+        match err {
+            ParseError::UnexpectedOption(_, suggestion) => assert_eq!(suggestion, None),
+            other => panic!("expected UnexpectedOption, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn suggests_keyboard_adjacent_short_option() {
+        let opts = Opts::new(vec![Opt::name("file").short('f')]).unwrap();
+        let args: Vec<String> = ["myprogram", "-g"].iter().map(|s| s.to_string()).collect();
+
+        let err = opts.parse(args).unwrap_err();
+        match err {
+            ParseError::UnexpectedOption(arg, suggestion) => {
+                assert_eq!(arg, "-g");
+                assert_eq!(suggestion, Some("-f".to_string()));
+            }
+            other => panic!("expected UnexpectedOption, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn does_not_suggest_unrelated_short_option() {
+        let opts = Opts::new(vec![Opt::name("file").short('f')]).unwrap();
+        let args: Vec<String> = ["myprogram", "-z"].iter().map(|s| s.to_string()).collect();
+
+        let err = opts.parse(args).unwrap_err();
+        match err {
+            ParseError::UnexpectedOption(_, suggestion) => assert_eq!(suggestion, None),
+            other => panic!("expected UnexpectedOption, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn suggests_vertically_adjacent_short_option() {
+        let opts = Opts::new(vec![Opt::name("all").short('a')]).unwrap();
+        let args: Vec<String> = ["myprogram", "-q"].iter().map(|s| s.to_string()).collect();
+
+        let err = opts.parse(args).unwrap_err();
+        match err {
+            ParseError::UnexpectedOption(arg, suggestion) => {
+                assert_eq!(arg, "-q");
+                assert_eq!(suggestion, Some("-a".to_string()));
+            }
+            other => panic!("expected UnexpectedOption, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn treats_everything_after_double_dash_as_positional() {
+        let opts = Opts::new(vec![Opt::name("verbose").long("verbose").action(Action::SetTrue)]).unwrap();
+        let args: Vec<String> = ["myprogram", "--verbose", "--", "--not-an-option", "-x"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.flag("verbose").unwrap(), Some(true));
+        assert_eq!(matches.positional(), vec!["--not-an-option", "-x"]);
+    }
+
+    #[test]
+    fn counts_repeated_clustered_flag() {
+        let opts = Opts::new(vec![Opt::name("verbosity").short('v').action(Action::Count)]).unwrap();
+        let args: Vec<String> = ["myprogram", "-vvv"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.count("verbosity").unwrap(), 3);
+    }
+
+    #[test]
+    fn count_defaults_to_zero_when_absent() {
+        let opts = Opts::new(vec![Opt::name("verbosity").short('v').action(Action::Count)]).unwrap();
+        let args: Vec<String> = ["myprogram"].iter().map(|s| s.to_string()).collect();
+
+        let matches = opts.parse(args).unwrap();
+        assert_eq!(matches.count("verbosity").unwrap(), 0);
+    }
+
+    #[test]
+    fn jaro_winkler_identical_strings_score_one() {
+        assert_eq!(jaro_winkler("host", "host"), 1.0);
+    }
+
+    #[test]
+    fn wraps_long_descriptions() {
+        let long_help = "a ".repeat(60) + "end";
+        let lines = wrap_text(&long_help, 20);
+        assert!(lines.iter().all(|l| l.chars().count() <= 20));
+        assert!(lines.last().unwrap().ends_with("end"));
+    }
 }